@@ -0,0 +1,426 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::{
+    DepartureInfo, DepartureQuery, Line, Location, MvgError, RetryPolicy, Station, StationGlobalId,
+};
+
+const DEFAULT_BASE_URL: &str = "https://www.mvg.de";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_USER_AGENT: &str = concat!("mvg-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Upper bound for the exponential backoff between retries, so a
+/// `RetryPolicy` with a large `max_attempts` still degrades gracefully
+/// instead of computing multi-year delays (or overflowing `u32::pow`).
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// A client for the MVG API that reuses a single [`reqwest::Client`] (and
+/// thus its connection pool) across requests.
+///
+/// Construct one with [`MvgClient::new`] for the defaults, or
+/// [`MvgClient::builder`] to point it at a different base URL (useful for
+/// tests that run against a mock server), timeout, or user agent.
+///
+/// Cloning an `MvgClient` is cheap: the underlying `reqwest::Client` is
+/// reference-counted internally, so clones share the same connection pool.
+#[derive(Clone)]
+pub struct MvgClient {
+    http: reqwest::Client,
+    base_url: String,
+    retry_policy: RetryPolicy,
+}
+
+impl MvgClient {
+    /// Create a client with the default base URL, timeout, and user agent.
+    ///
+    /// # Panics
+    /// Panics if the underlying `reqwest::Client` fails to build, e.g. if
+    /// the platform's TLS backend cannot be initialized.
+    pub fn new() -> Self {
+        Self::builder()
+            .build()
+            .expect("failed to build the default reqwest client")
+    }
+
+    /// Start building a client with a custom base URL, timeout, or user agent.
+    pub fn builder() -> MvgClientBuilder {
+        MvgClientBuilder::default()
+    }
+
+    /// Retrieve a list of all stations.
+    pub async fn request_stations(&self) -> Result<Vec<Station>, MvgError> {
+        self.fetch_json(format!("{}/.rest/zdm/stations", self.base_url))
+            .await
+    }
+
+    /// Retrieve a list of all station global ids.
+    pub async fn request_station_global_ids(&self) -> Result<Vec<StationGlobalId>, MvgError> {
+        self.fetch_json(format!("{}/.rest/zdm/mvgStationGlobalIds", self.base_url))
+            .await
+    }
+
+    /// Retrieve a list of all lines.
+    pub async fn request_lines(&self) -> Result<Vec<Line>, MvgError> {
+        self.fetch_json(format!("{}/.rest/zdm/lines", self.base_url))
+            .await
+    }
+
+    /// Retrieve upcoming departures for a station.
+    pub async fn request_departures<S: Into<String>>(
+        &self,
+        global_id: S,
+    ) -> Result<Vec<DepartureInfo>, MvgError> {
+        let url = format!(
+            "{}/api/fib/v2/departure?globalId={}",
+            self.base_url,
+            global_id.into()
+        );
+        self.fetch_json(url).await
+    }
+
+    /// Retrieve upcoming departures for a station, filtered by transport
+    /// type, result count, and/or how far out to look.
+    pub async fn request_departures_with(
+        &self,
+        query: DepartureQuery,
+    ) -> Result<Vec<DepartureInfo>, MvgError> {
+        let url = format!(
+            "{}/api/fib/v2/departure?{}",
+            self.base_url,
+            query.to_query_string()
+        );
+        self.fetch_json(url).await
+    }
+
+    /// Find a location using a query string.
+    ///
+    /// Returns a list of locations, where the first element is the best match.
+    pub async fn find_location<S: Into<String>>(&self, query: S) -> Result<Vec<Location>, MvgError> {
+        let url = format!("{}/api/fib/v2/location?query={}", self.base_url, query.into());
+        let locations: Vec<Location> = self.fetch_json(url).await?;
+
+        if locations.is_empty() {
+            return Err(MvgError::NotFound);
+        }
+
+        Ok(locations)
+    }
+
+    /// Find a nearby location via latitude and longitude.
+    ///
+    /// Returns a list of locations, where the first element is the best match.
+    pub async fn find_nearby_location(
+        &self,
+        latitude: f32,
+        longitude: f32,
+    ) -> Result<Vec<Location>, MvgError> {
+        let url = format!(
+            "{}/api/fib/v2/station/nearby?latitude={}&longitude={}",
+            self.base_url, latitude, longitude
+        );
+        let locations: Vec<Location> = self.fetch_json(url).await?;
+
+        if locations.is_empty() {
+            return Err(MvgError::NotFound);
+        }
+
+        Ok(locations)
+    }
+
+    /// Fetch `url` and deserialize the body as `T`, translating transport and
+    /// rate-limit failures into [`MvgError`] and retrying per
+    /// [`Self::retry_policy`] instead of panicking on a bad status.
+    async fn fetch_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: impl reqwest::IntoUrl,
+    ) -> Result<T, MvgError> {
+        let url = url.into_url()?;
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            match self.fetch_json_once(url.clone()).await {
+                Ok(value) => return Ok(value),
+                Err(err) => {
+                    match retry_decision(attempt, self.retry_policy.max_attempts, is_retryable(&err)) {
+                        RetryDecision::Retry => {
+                            tokio::time::sleep(self.backoff_delay(attempt, &err)).await;
+                        }
+                        RetryDecision::Fail => return Err(err),
+                        RetryDecision::GiveUp { attempts } => {
+                            return Err(MvgError::RetriesExhausted {
+                                attempts,
+                                source: Box::new(err),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn fetch_json_once<T: serde::de::DeserializeOwned>(
+        &self,
+        url: reqwest::Url,
+    ) -> Result<T, MvgError> {
+        let resp = self.http.get(url).send().await?;
+
+        if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
+            return Err(MvgError::RateLimited { retry_after });
+        }
+
+        let bytes = resp.error_for_status()?.bytes().await?;
+        serde_json::from_slice(&bytes).map_err(MvgError::Deserialize)
+    }
+
+    /// The delay before the next retry: the `Retry-After` header for a 429,
+    /// or an exponential backoff with jitter otherwise, capped at
+    /// [`MAX_BACKOFF`] so a high `max_attempts` can't compute an
+    /// unreasonably long (or overflowing) delay.
+    fn backoff_delay(&self, attempt: u32, err: &MvgError) -> Duration {
+        if let MvgError::RateLimited {
+            retry_after: Some(retry_after),
+        } = err
+        {
+            return *retry_after;
+        }
+
+        let exponential = 2u32
+            .checked_pow(attempt - 1)
+            .and_then(|factor| self.retry_policy.base_delay.checked_mul(factor))
+            .map_or(MAX_BACKOFF, |delay| delay.min(MAX_BACKOFF));
+        let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..=exponential.as_millis() as u64 / 2));
+        exponential + jitter
+    }
+}
+
+/// Parse a `Retry-After` header value as delta-seconds.
+///
+/// The header may also be an HTTP-date (RFC 7231 section 7.1.3); that form
+/// isn't parsed and yields `None` here, falling back to
+/// [`MvgClient::backoff_delay`]'s exponential backoff instead.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Whether a failed request is worth retrying: connection errors, timeouts,
+/// `5xx` responses, and rate limiting, but not a client error or a body we
+/// failed to parse.
+fn is_retryable(err: &MvgError) -> bool {
+    match err {
+        MvgError::Http(err) => {
+            err.is_connect() || err.is_timeout() || err.status().is_some_and(|s| s.is_server_error())
+        }
+        MvgError::RateLimited { .. } => true,
+        MvgError::Deserialize(_) | MvgError::NotFound | MvgError::RetriesExhausted { .. } => false,
+    }
+}
+
+/// What `fetch_json` should do after a failed attempt.
+#[derive(Debug, PartialEq, Eq)]
+enum RetryDecision {
+    /// Sleep and try again.
+    Retry,
+    /// The error isn't retryable; return it as-is regardless of `attempt`.
+    Fail,
+    /// The error is retryable but the policy is exhausted; wrap it.
+    GiveUp { attempts: u32 },
+}
+
+/// Decide what to do after attempt number `attempt` (1-based) failed with an
+/// error for which `retryable` was already determined by [`is_retryable`].
+///
+/// `GiveUp` only fires once at least one retry was actually attempted: with
+/// [`RetryPolicy::none`](crate::RetryPolicy::none) (`max_attempts == 1`), a
+/// retryable error on the one and only attempt is a plain `Fail`, matching
+/// that policy's documented "first failure is returned as-is" contract
+/// rather than being wrapped as `RetriesExhausted` after zero retries.
+fn retry_decision(attempt: u32, max_attempts: u32, retryable: bool) -> RetryDecision {
+    if !retryable {
+        RetryDecision::Fail
+    } else if attempt < max_attempts {
+        RetryDecision::Retry
+    } else if attempt > 1 {
+        RetryDecision::GiveUp { attempts: attempt }
+    } else {
+        RetryDecision::Fail
+    }
+}
+
+impl Default for MvgClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds a [`MvgClient`] with a custom base URL, timeout, user agent, or
+/// retry policy.
+pub struct MvgClientBuilder {
+    base_url: String,
+    timeout: Duration,
+    user_agent: String,
+    retry_policy: RetryPolicy,
+}
+
+impl Default for MvgClientBuilder {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+}
+
+impl MvgClientBuilder {
+    /// Override the base URL requests are made against, e.g. to point at a
+    /// mock server in tests. Defaults to `https://www.mvg.de`.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Override the per-request timeout. Defaults to 10 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Override the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Override the retry policy applied to failed requests. Defaults to
+    /// [`RetryPolicy::default`].
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Build the client.
+    pub fn build(self) -> Result<MvgClient, MvgError> {
+        let http = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .user_agent(self.user_agent)
+            .build()?;
+
+        Ok(MvgClient {
+            http,
+            base_url: self.base_url,
+            retry_policy: self.retry_policy,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retryable_error_retries_until_max_attempts_then_gives_up() {
+        assert_eq!(retry_decision(1, 3, true), RetryDecision::Retry);
+        assert_eq!(retry_decision(2, 3, true), RetryDecision::Retry);
+        assert_eq!(
+            retry_decision(3, 3, true),
+            RetryDecision::GiveUp { attempts: 3 }
+        );
+    }
+
+    #[test]
+    fn retryable_error_fails_without_wrapping_when_policy_allows_no_retries() {
+        // RetryPolicy::none() (max_attempts == 1): a retryable error on the
+        // only attempt must come back bare, not as RetriesExhausted, since
+        // no retry was ever actually attempted.
+        assert_eq!(retry_decision(1, 1, true), RetryDecision::Fail);
+    }
+
+    #[test]
+    fn non_retryable_error_fails_immediately_regardless_of_attempt() {
+        // A 404 on attempt 1 behaves the same as a 404 on a later attempt
+        // (e.g. after an earlier 503 was retried): it must never be wrapped
+        // as `RetriesExhausted`.
+        assert_eq!(retry_decision(1, 3, false), RetryDecision::Fail);
+        assert_eq!(retry_decision(2, 3, false), RetryDecision::Fail);
+        assert_eq!(retry_decision(3, 3, false), RetryDecision::Fail);
+    }
+
+    #[test]
+    fn parse_retry_after_accepts_delta_seconds() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn parse_retry_after_rejects_http_date() {
+        // The HTTP-date form (RFC 7231 section 7.1.3) isn't supported.
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:28:00 GMT"),
+            None
+        );
+    }
+
+    #[test]
+    fn is_retryable_accepts_rate_limited() {
+        assert!(is_retryable(&MvgError::RateLimited { retry_after: None }));
+    }
+
+    #[test]
+    fn is_retryable_rejects_not_found_and_deserialize_errors() {
+        assert!(!is_retryable(&MvgError::NotFound));
+
+        let deserialize_err = serde_json::from_str::<i32>("not json").unwrap_err();
+        assert!(!is_retryable(&MvgError::Deserialize(deserialize_err)));
+    }
+
+    #[test]
+    fn backoff_delay_honors_retry_after_header() {
+        let client = MvgClient::builder().build().unwrap();
+        let retry_after = Duration::from_secs(42);
+        let err = MvgError::RateLimited {
+            retry_after: Some(retry_after),
+        };
+
+        assert_eq!(client.backoff_delay(1, &err), retry_after);
+    }
+
+    #[test]
+    fn backoff_delay_grows_exponentially_with_the_base_delay() {
+        let base_delay = Duration::from_millis(100);
+        let client = MvgClient::builder()
+            .retry_policy(RetryPolicy::new(5, base_delay))
+            .build()
+            .unwrap();
+        let err = MvgError::RateLimited { retry_after: None };
+
+        // Jitter adds up to half of the exponential component, never less.
+        let first = client.backoff_delay(1, &err);
+        assert!(first >= base_delay && first <= base_delay * 3 / 2);
+
+        let second = client.backoff_delay(2, &err);
+        let exponential = base_delay * 2;
+        assert!(second >= exponential && second <= exponential * 3 / 2);
+    }
+
+    #[test]
+    fn backoff_delay_is_capped_at_high_attempt_counts() {
+        // `2u32.pow(attempt - 1)` would overflow well before attempt 40;
+        // the delay must stay capped at MAX_BACKOFF (plus jitter) instead of
+        // panicking (debug) or wrapping to a near-zero delay (release).
+        let client = MvgClient::builder()
+            .retry_policy(RetryPolicy::new(40, Duration::from_millis(100)))
+            .build()
+            .unwrap();
+        let err = MvgError::RateLimited { retry_after: None };
+
+        let delay = client.backoff_delay(40, &err);
+        assert!(delay >= MAX_BACKOFF && delay <= MAX_BACKOFF * 3 / 2);
+    }
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+/// The error type returned by all fallible functions in this crate.
+#[derive(thiserror::Error, Debug)]
+pub enum MvgError {
+    /// The request could not be sent, the server responded with an error
+    /// status other than 429, or the response could not be read.
+    #[error("http request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The response body could not be deserialized into the expected type.
+    #[error("failed to deserialize response: {0}")]
+    Deserialize(#[from] serde_json::Error),
+
+    /// The server responded with an empty or otherwise unusable result.
+    #[error("no matching result was found")]
+    NotFound,
+
+    /// The server responded with HTTP 429 ("Too Many Requests").
+    #[error("rate limited{}", .retry_after.map(|d| format!(", retry after {d:?}")).unwrap_or_default())]
+    RateLimited {
+        /// The duration indicated by the `Retry-After` header, if present.
+        retry_after: Option<Duration>,
+    },
+
+    /// A [`RetryPolicy`](crate::RetryPolicy) was exhausted without a
+    /// successful response.
+    #[error("request failed after {attempts} attempts: {source}")]
+    RetriesExhausted {
+        /// The number of attempts that were made, including the first.
+        attempts: u32,
+        #[source]
+        source: Box<MvgError>,
+    },
+}
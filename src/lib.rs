@@ -1,11 +1,36 @@
+use std::sync::OnceLock;
+
 use serde::{Deserialize, Serialize};
 
-const MVG_LOCATION: &str = "https://www.mvg.de/api/fib/v2/location";
-const MVG_STATION_NEARBY: &str = "https://www.mvg.de/api/fib/v2/station/nearby";
-const MVG_DEPARTURE: &str = "https://www.mvg.de/api/fib/v2/departure";
-const MVG_STATIONS: &str = "https://www.mvg.de/.rest/zdm/stations";
-const MVG_STATION_GLOBAL_IDS: &str = "https://www.mvg.de/.rest/zdm/mvgStationGlobalIds";
-const MVG_LINES: &str = "https://www.mvg.de/.rest/zdm/lines";
+mod client;
+mod error;
+mod monitor;
+mod query;
+mod retry;
+
+pub use client::{MvgClient, MvgClientBuilder};
+pub use error::MvgError;
+pub use monitor::DepartureMonitor;
+pub use query::DepartureQuery;
+pub use retry::RetryPolicy;
+
+/// The [`MvgClient`] used by the free functions in this crate, built lazily
+/// with defaults on first use.
+///
+/// Unlike [`MvgClient::new`], this never panics: if building the default
+/// client fails (e.g. the platform's TLS backend can't be initialized), the
+/// error is returned to the caller, just as it was when these functions
+/// called `reqwest::get` directly. A later call tries again.
+fn default_client() -> Result<&'static MvgClient, MvgError> {
+    static CLIENT: OnceLock<MvgClient> = OnceLock::new();
+
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+
+    let client = MvgClient::builder().build()?;
+    Ok(CLIENT.get_or_init(|| client))
+}
 
 /// Represents a MVG station ("Haltestelle").
 ///
@@ -37,25 +62,18 @@ pub struct Station {
 }
 
 /// Retrieve a list of all stations.
-pub async fn request_stations() -> Result<Vec<Station>, Box<dyn std::error::Error>> {
-    let resp = reqwest::get(MVG_STATIONS).await?;
-    let stations = resp.json::<Vec<Station>>().await?;
-
-    Ok(stations)
+pub async fn request_stations() -> Result<Vec<Station>, MvgError> {
+    default_client()?.request_stations().await
 }
 
 /// Represents a MVG global station id.
 ///
 /// Examples of valid ids are "de:09162:1" and "de:09162:9029".
-type StationGlobalId = String;
+pub(crate) type StationGlobalId = String;
 
 /// Retrieve a list of all station global ids.
-pub async fn request_station_global_ids() -> Result<Vec<StationGlobalId>, Box<dyn std::error::Error>>
-{
-    let resp = reqwest::get(MVG_STATION_GLOBAL_IDS).await?;
-    let ids = resp.json::<Vec<StationGlobalId>>().await?;
-
-    Ok(ids)
+pub async fn request_station_global_ids() -> Result<Vec<StationGlobalId>, MvgError> {
+    default_client()?.request_station_global_ids().await
 }
 
 /// Represents a MVG line.
@@ -80,11 +98,8 @@ pub struct Line {
 }
 
 /// Retrieve a list of all lines.
-pub async fn request_lines() -> Result<Vec<Line>, Box<dyn std::error::Error>> {
-    let resp = reqwest::get(MVG_LINES).await?;
-    let lines = resp.json::<Vec<Line>>().await?;
-
-    Ok(lines)
+pub async fn request_lines() -> Result<Vec<Line>, MvgError> {
+    default_client()?.request_lines().await
 }
 
 /// Represents information about an upcoming departure.
@@ -110,7 +125,7 @@ pub async fn request_lines() -> Result<Vec<Line>, Box<dyn std::error::Error>> {
 ///  :trainType "",
 ///  :transportType "SBAHN"}
 /// ```
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DepartureInfo {
     pub banner_hash: Option<String>,
@@ -134,15 +149,57 @@ pub struct DepartureInfo {
     pub transport_type: Option<String>,
 }
 
+#[cfg(feature = "chrono")]
+impl DepartureInfo {
+    /// The scheduled departure time in the `Europe/Berlin` timezone.
+    pub fn planned_departure(&self) -> Option<chrono::DateTime<chrono_tz::Tz>> {
+        Self::to_berlin_time(self.planned_departure_time)
+    }
+
+    /// The realtime-predicted departure time in the `Europe/Berlin` timezone.
+    pub fn realtime_departure(&self) -> Option<chrono::DateTime<chrono_tz::Tz>> {
+        Self::to_berlin_time(self.realtime_departure_time)
+    }
+
+    /// The time remaining until this departure, as of `now`.
+    ///
+    /// Prefers [`Self::realtime_departure`] and falls back to
+    /// [`Self::planned_departure`] plus [`Self::delay_in_minutes`]. Returns
+    /// `None` if the departure is cancelled, so a UI doesn't keep counting
+    /// down towards a train that will never come.
+    pub fn time_until_departure(
+        &self,
+        now: chrono::DateTime<chrono_tz::Tz>,
+    ) -> Option<chrono::Duration> {
+        if self.cancelled == Some(true) {
+            return None;
+        }
+
+        let departure = self.realtime_departure().or_else(|| {
+            self.planned_departure()
+                .map(|time| time + chrono::Duration::minutes(self.delay_in_minutes.unwrap_or(0) as i64))
+        })?;
+
+        Some(departure - now)
+    }
+
+    fn to_berlin_time(epoch_millis: Option<i64>) -> Option<chrono::DateTime<chrono_tz::Tz>> {
+        let utc = chrono::DateTime::from_timestamp_millis(epoch_millis?)?;
+        Some(utc.with_timezone(&chrono_tz::Europe::Berlin))
+    }
+}
+
 /// Retrieve upcoming departures for a station.
 pub async fn request_departures<S: Into<String>>(
     global_id: S,
-) -> Result<Vec<DepartureInfo>, Box<dyn std::error::Error>> {
-    let url = format!("{}?globalId={}", MVG_DEPARTURE, global_id.into());
-    let resp = reqwest::get(url).await?;
-    let departures = resp.json::<Vec<DepartureInfo>>().await?;
+) -> Result<Vec<DepartureInfo>, MvgError> {
+    default_client()?.request_departures(global_id).await
+}
 
-    Ok(departures)
+/// Retrieve upcoming departures for a station, filtered by transport type,
+/// result count, and/or how far out to look. See [`DepartureQuery`].
+pub async fn request_departures_with(query: DepartureQuery) -> Result<Vec<DepartureInfo>, MvgError> {
+    default_client()?.request_departures_with(query).await
 }
 
 /// Represents information about a location.
@@ -183,32 +240,16 @@ pub struct Location {
 /// Find a location using a query string.
 ///
 /// Returns a list of locations, where the first element is the best match.
-pub async fn find_location<S: Into<String>>(
-    query: S,
-) -> Result<Vec<Location>, Box<dyn std::error::Error>> {
-    let url = format!("{}?query={}", MVG_LOCATION, query.into());
-    let resp = reqwest::get(url).await?;
-    let locations = resp.json::<Vec<Location>>().await?;
-
-    Ok(locations)
+pub async fn find_location<S: Into<String>>(query: S) -> Result<Vec<Location>, MvgError> {
+    default_client()?.find_location(query).await
 }
 
 /// Find a nearby location via latitude and longitude.
 ///
 /// Returns a list of locations, where the first element is the best match.
 pub async fn find_nearby_location(
-    latitude: f32, longitude: f32
-) -> Result<Vec<Location>, Box<dyn std::error::Error>> {
-    let url = format!("{}?latitude={}&longitude={}", MVG_STATION_NEARBY, latitude, longitude);
-    let resp = reqwest::get(url).await?;
-    let locations = resp.json::<Vec<Location>>().await?;
-
-    Ok(locations)
-}
-
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+    latitude: f32,
+    longitude: f32,
+) -> Result<Vec<Location>, MvgError> {
+    default_client()?.find_nearby_location(latitude, longitude).await
 }
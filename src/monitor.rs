@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::{DepartureInfo, MvgClient, MvgError};
+
+/// Upper bound for the exponential backoff applied after repeated failures,
+/// so a prolonged outage still gets polled every so often.
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// Polls a station's departures on an interval and publishes each snapshot.
+///
+/// Identical consecutive snapshots are suppressed, so subscribers only wake
+/// up when something actually changes. A failed poll is published as an
+/// `Err` rather than stopping the monitor, and repeated failures back off
+/// exponentially (capped) so a flaky network isn't hammered with requests.
+///
+/// The background task is aborted when the `DepartureMonitor` is dropped.
+pub struct DepartureMonitor {
+    updates: watch::Receiver<Option<Result<Vec<DepartureInfo>, MvgError>>>,
+    task: JoinHandle<()>,
+}
+
+impl DepartureMonitor {
+    /// Start polling `global_id` on `client` every `poll_interval`.
+    pub fn spawn(client: MvgClient, global_id: impl Into<String>, poll_interval: Duration) -> Self {
+        let global_id = global_id.into();
+        let (tx, rx) = watch::channel(None);
+
+        let task = tokio::spawn(async move {
+            let mut last_ok: Option<Vec<DepartureInfo>> = None;
+            let mut backoff = poll_interval;
+
+            loop {
+                match client.request_departures(global_id.clone()).await {
+                    Ok(departures) => {
+                        backoff = poll_interval;
+
+                        if last_ok.as_ref() != Some(&departures) {
+                            last_ok = Some(departures.clone());
+                            if tx.send(Some(Ok(departures))).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        last_ok = None;
+                        if tx.send(Some(Err(err))).is_err() {
+                            break;
+                        }
+                        backoff = (backoff * 2).min(MAX_BACKOFF);
+                    }
+                }
+
+                tokio::time::sleep(backoff).await;
+            }
+        });
+
+        Self { updates: rx, task }
+    }
+
+    /// Subscribe to departure snapshots.
+    ///
+    /// The receiver yields `None` until the first poll completes, then
+    /// `Some(Ok(departures))` for each distinct snapshot or `Some(Err(_))`
+    /// whenever a poll fails.
+    pub fn updates(&self) -> watch::Receiver<Option<Result<Vec<DepartureInfo>, MvgError>>> {
+        self.updates.clone()
+    }
+}
+
+impl Drop for DepartureMonitor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
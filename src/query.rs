@@ -0,0 +1,87 @@
+/// Builds a filtered query for [`crate::MvgClient::request_departures_with`].
+///
+/// Only `global_id` is required; `transport_types`, `limit`, and
+/// `offset_in_minutes` are left to the server's defaults (no filter, no
+/// limit, now) unless set.
+#[derive(Debug, Clone)]
+pub struct DepartureQuery {
+    global_id: String,
+    transport_types: Vec<String>,
+    limit: Option<u32>,
+    offset_in_minutes: Option<u32>,
+}
+
+impl DepartureQuery {
+    /// Start a query for the station with the given global id.
+    pub fn new(global_id: impl Into<String>) -> Self {
+        Self {
+            global_id: global_id.into(),
+            transport_types: Vec::new(),
+            limit: None,
+            offset_in_minutes: None,
+        }
+    }
+
+    /// Only include departures of the given transport types, e.g. `"SBAHN"`
+    /// or `"UBAHN"`.
+    pub fn transport_types(mut self, transport_types: Vec<String>) -> Self {
+        self.transport_types = transport_types;
+        self
+    }
+
+    /// Limit the number of returned departures.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Only include departures at least this many minutes from now.
+    pub fn offset_in_minutes(mut self, offset_in_minutes: u32) -> Self {
+        self.offset_in_minutes = Some(offset_in_minutes);
+        self
+    }
+
+    /// Render the query as a URL query string, e.g.
+    /// `globalId=de:09162:1&limit=10`.
+    pub(crate) fn to_query_string(&self) -> String {
+        let mut query = format!("globalId={}", self.global_id);
+
+        if !self.transport_types.is_empty() {
+            query.push_str("&transportTypes=");
+            query.push_str(&self.transport_types.join(","));
+        }
+        if let Some(limit) = self.limit {
+            query.push_str(&format!("&limit={limit}"));
+        }
+        if let Some(offset_in_minutes) = self.offset_in_minutes {
+            query.push_str(&format!("&offsetInMinutes={offset_in_minutes}"));
+        }
+
+        query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_query_string_with_only_the_required_global_id() {
+        let query = DepartureQuery::new("de:09162:1");
+
+        assert_eq!(query.to_query_string(), "globalId=de:09162:1");
+    }
+
+    #[test]
+    fn to_query_string_includes_every_set_filter() {
+        let query = DepartureQuery::new("de:09162:1")
+            .transport_types(vec!["SBAHN".to_string(), "UBAHN".to_string()])
+            .limit(10)
+            .offset_in_minutes(20);
+
+        assert_eq!(
+            query.to_query_string(),
+            "globalId=de:09162:1&transportTypes=SBAHN,UBAHN&limit=10&offsetInMinutes=20"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+/// Governs how [`crate::MvgClient`] retries a failed request.
+///
+/// Connection errors and `5xx` responses are tried up to `max_attempts`
+/// times in total (the first attempt included), with an exponential backoff
+/// (plus jitter) starting at `base_delay`. A `429` response is retried
+/// honoring its `Retry-After` header, when it carries one in delta-seconds
+/// form, instead of the computed backoff. Other `4xx` responses are never
+/// retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub(crate) max_attempts: u32,
+    pub(crate) base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// A policy that never retries; the first failure is returned as-is.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay: Duration::ZERO,
+        }
+    }
+
+    /// Retry up to `max_attempts` times (including the first attempt), with
+    /// backoff starting at `base_delay` and doubling on each subsequent
+    /// attempt.
+    pub fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, starting at a 200ms backoff.
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(200))
+    }
+}